@@ -1,9 +1,11 @@
 extern crate lmdb_rs as lmdb;
+extern crate rkyv;
 
 use lmdb::{FromMdbValue, ToMdbValue, MdbValue,
            DbFlags, DbHandle, Database, Environment, Cursor, MDB_val};
 use lmdb::core::MdbResult;
 use std::marker::PhantomData;
+use std::ops::Bound;
 
 #[macro_export]
 macro_rules! lmdb_not_found {
@@ -32,6 +34,11 @@ macro_rules! impl_table {
 }
 
 /// Defines all neccessary information to create/open a database.
+///
+/// `setup` is also where a custom ordering is installed: call
+/// `db.set_dupsort(...)` to control duplicate-value order, or
+/// `db.set_compare(...)` to control the primary key order (see
+/// `compare_key!` for composite keys).
 pub trait TableDef {
     fn name() -> &'static str;
     fn flags() -> DbFlags;
@@ -72,6 +79,33 @@ where K: FromMdbValue + ToMdbValue,
         self.db.insert(key, value)
     }
 
+    /// Appends `key`/`value` using `MDB_APPEND`, skipping the B-tree search
+    /// that `set`/`insert` perform. `key` must be greater than every key
+    /// already in the table; LMDB returns an error otherwise. Intended for
+    /// bulk-loading data that is already sorted by key.
+    #[inline(always)]
+    pub fn append(&self, key: &K, value: &V) -> MdbResult<()> {
+        self.db.append(key, value)
+    }
+
+    /// Appends a duplicate value for `key` using `MDB_APPENDDUP`, the
+    /// `DUP_SORT` analogue of `append`: `value` must sort after every
+    /// existing duplicate of `key`.
+    #[inline(always)]
+    pub fn append_dup(&self, key: &K, value: &V) -> MdbResult<()> {
+        self.db.append_duplicate(key, value)
+    }
+
+    /// Bulk-loads `items`, which must already be sorted by key, using
+    /// `append` for each pair. Returns an error as soon as an out-of-order
+    /// key is encountered, since LMDB rejects `MDB_APPEND` in that case.
+    pub fn extend<I: IntoIterator<Item=(K, V)>>(&self, items: I) -> MdbResult<()> {
+        for (k, v) in items {
+            try!(self.append(&k, &v));
+        }
+        Ok(())
+    }
+
     /// Checks if the item exists.
     pub fn insert_item(&self, key: &K, value: &V) -> MdbResult<()> {
         if try!(self.has_item(key, value)) {
@@ -131,6 +165,137 @@ where K: FromMdbValue + ToMdbValue,
         try!(cursor.to_key(k));
         Ok(cursor)
     }
+
+    /// Iterates over the whole table in key order. For `DUP_SORT` tables,
+    /// all duplicate values of a key are yielded before the iterator moves
+    /// on to the next key.
+    pub fn iter<'table>(&'table self) -> MdbResult<TypedIter<'table, K, V>> {
+        Ok(TypedIter {cursor: try!(self.new_cursor()), state: IterState::NotStarted})
+    }
+
+    /// Iterates starting at the first key greater than or equal to `key`,
+    /// using `to_gte_key` to position the underlying cursor.
+    pub fn iter_from<'table>(&'table self, key: &K) -> MdbResult<TypedIter<'table, K, V>> {
+        let mut cursor = try!(self.new_cursor());
+        match cursor.to_gte_key(key) {
+            Ok(()) => Ok(TypedIter {cursor: cursor, state: IterState::PositionedUnread}),
+            Err(::lmdb::MdbError::NotFound) => Ok(TypedIter {cursor: cursor, state: IterState::Done}),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Iterates over the items whose key falls within `lo..hi`, honoring
+    /// `Bound::Included`/`Bound::Excluded`/`Bound::Unbounded` on both ends.
+    pub fn range<'table>(&'table self, lo: Bound<&'table K>, hi: Bound<&'table K>) -> MdbResult<RangeIter<'table, K, V>>
+    where K: Ord
+    {
+        let inner = match lo {
+            Bound::Unbounded => try!(self.iter()),
+            Bound::Included(k) | Bound::Excluded(k) => try!(self.iter_from(k)),
+        };
+        Ok(RangeIter {inner: inner, lo: lo, hi: hi, done: false})
+    }
+}
+
+/// Zero-copy access to values stored as `rkyv` archives.
+///
+/// `V` only needs to be `rkyv`-archivable, not `FromMdbValue`/`ToMdbValue`:
+/// on write the value is serialized into a temporary heap buffer whose bytes
+/// are then copied into LMDB as the value,
+/// and on read the archived representation is cast directly out of the
+/// memory-mapped page, without allocating or deserializing. The returned
+/// reference borrows from the underlying transaction and must not outlive it.
+impl<'db, K, V> Table<'db, K, V>
+where K: FromMdbValue + ToMdbValue,
+      V: rkyv::Archive,
+{
+    /// Serializes `value` with `rkyv` and stores the resulting bytes under `key`.
+    pub fn set_archived(&self, key: &K, value: &V) -> MdbResult<()>
+    where V: rkyv::Serialize<rkyv::ser::serializers::AllocSerializer<256>>
+    {
+        let bytes = rkyv::to_bytes::<_, 256>(value).unwrap();
+        self.db.set(key, &bytes.as_ref())
+    }
+
+    /// Returns a reference to the archived value stored under `key`, cast
+    /// directly out of the memory-mapped page. The reference borrows from
+    /// `&self` and so cannot outlive the table's transaction.
+    pub fn get_archived<'a>(&'a self, key: &K) -> MdbResult<&'a V::Archived>
+    where V::Archived: 'a
+    {
+        let bytes: &'a [u8] = try!(self.db.get(key));
+        Ok(unsafe { rkyv::archived_root::<V>(bytes) })
+    }
+
+    /// Iterates the whole table, yielding `(key, archived value)` pairs in
+    /// key order.
+    pub fn iter_archived<'table>(&'table self) -> MdbResult<ArchivedIter<'table, K, V>> {
+        Ok(ArchivedIter {cursor: try!(self.db.new_cursor()), k: PhantomData, v: PhantomData, state: IterState::NotStarted})
+    }
+}
+
+/// Iterator over archived `(K, &V::Archived)` pairs, produced by `Table::iter_archived`.
+///
+/// Advances the same way as `TypedIter`: `to_first`/`to_next_key` move
+/// between keys, and `to_next_item` only walks duplicates within a key,
+/// so all of a `DUP_SORT` key's values are yielded before moving on.
+pub struct ArchivedIter<'table, K, V> {
+    cursor: Cursor<'table>,
+    k: PhantomData<K>,
+    v: PhantomData<V>,
+    state: IterState,
+}
+
+impl<'table, K: FromMdbValue+ToMdbValue, V: rkyv::Archive> Iterator for ArchivedIter<'table, K, V>
+where V::Archived: 'table
+{
+    type Item = MdbResult<(K, &'table V::Archived)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.state {
+            IterState::Done => return None,
+            IterState::PositionedUnread => {
+                self.state = IterState::Positioned;
+            }
+            IterState::NotStarted => {
+                self.state = IterState::Positioned;
+                if let Err(e) = self.cursor.to_first() {
+                    self.state = IterState::Done;
+                    return match e {
+                        ::lmdb::MdbError::NotFound => None,
+                        _ => Some(Err(e)),
+                    };
+                }
+            }
+            IterState::Positioned => {
+                match self.cursor.to_next_item() {
+                    Ok(()) => {}
+                    Err(::lmdb::MdbError::NotFound) => {
+                        if let Err(e) = self.cursor.to_next_key() {
+                            self.state = IterState::Done;
+                            return match e {
+                                ::lmdb::MdbError::NotFound => None,
+                                _ => Some(Err(e)),
+                            };
+                        }
+                    }
+                    Err(e) => {
+                        self.state = IterState::Done;
+                        return Some(Err(e));
+                    }
+                }
+            }
+        }
+        let key: K = match self.cursor.get_key() {
+            Ok(k) => k,
+            Err(e) => { self.state = IterState::Done; return Some(Err(e)); }
+        };
+        let bytes: &'table [u8] = match self.cursor.get_value() {
+            Ok(b) => b,
+            Err(e) => { self.state = IterState::Done; return Some(Err(e)); }
+        };
+        Some(Ok((key, unsafe { rkyv::archived_root::<V>(bytes) })))
+    }
 }
 
 /// Is a typed version of lmdb::Cursor.
@@ -252,6 +417,115 @@ impl<'table, K: FromMdbValue+ToMdbValue, V: FromMdbValue+ToMdbValue> TypedCursor
     }
 }
 
+enum IterState {
+    NotStarted,
+    PositionedUnread,
+    Positioned,
+    Done,
+}
+
+/// Forward iterator over a `Table`, produced by `Table::iter`/`Table::iter_from`.
+///
+/// Wraps a `TypedCursor`, advancing between keys with `to_first`/
+/// `to_next_key` and, within a key, walking duplicates with
+/// `to_next_item` until they're exhausted — so for `DUP_SORT` tables all
+/// duplicate values of a key are yielded before the cursor moves to the
+/// next key. `MdbError::NotFound` maps to the end of the iterator.
+pub struct TypedIter<'table, K, V> {
+    cursor: TypedCursor<'table, K, V>,
+    state: IterState,
+}
+
+impl<'table, K: FromMdbValue+ToMdbValue, V: FromMdbValue+ToMdbValue> Iterator for TypedIter<'table, K, V> {
+    type Item = MdbResult<(K, V)>;
+
+    fn next(&mut self) -> Option<MdbResult<(K, V)>> {
+        match self.state {
+            IterState::Done => return None,
+            // The cursor is already sitting on the first matching item
+            // (positioned there by `iter_from`'s `to_gte_key`); read it
+            // without stepping first, or we'd skip it.
+            IterState::PositionedUnread => {
+                self.state = IterState::Positioned;
+                return Some(self.cursor.get());
+            }
+            IterState::NotStarted => {
+                self.state = IterState::Positioned;
+                if let Err(e) = self.cursor.to_first() {
+                    self.state = IterState::Done;
+                    return match e {
+                        ::lmdb::MdbError::NotFound => None,
+                        _ => Some(Err(e)),
+                    };
+                }
+            }
+            IterState::Positioned => {
+                // Walk the remaining duplicates of the current key first...
+                match self.cursor.to_next_item() {
+                    Ok(()) => {}
+                    Err(::lmdb::MdbError::NotFound) => {
+                        // ...then move on to the first item of the next key.
+                        if let Err(e) = self.cursor.to_next_key() {
+                            self.state = IterState::Done;
+                            return match e {
+                                ::lmdb::MdbError::NotFound => None,
+                                _ => Some(Err(e)),
+                            };
+                        }
+                    }
+                    Err(e) => {
+                        self.state = IterState::Done;
+                        return Some(Err(e));
+                    }
+                }
+            }
+        }
+        Some(self.cursor.get())
+    }
+}
+
+/// Iterator over a key range, produced by `Table::range`.
+///
+/// Drives a `TypedIter` from the lower bound and stops, without yielding,
+/// as soon as a decoded key falls outside the upper bound.
+pub struct RangeIter<'table, K: 'table, V> {
+    inner: TypedIter<'table, K, V>,
+    lo: Bound<&'table K>,
+    hi: Bound<&'table K>,
+    done: bool,
+}
+
+impl<'table, K: FromMdbValue+ToMdbValue+Ord, V: FromMdbValue+ToMdbValue> Iterator for RangeIter<'table, K, V> {
+    type Item = MdbResult<(K, V)>;
+
+    fn next(&mut self) -> Option<MdbResult<(K, V)>> {
+        if self.done {
+            return None;
+        }
+        loop {
+            match self.inner.next() {
+                None => { self.done = true; return None; }
+                Some(Err(e)) => { self.done = true; return Some(Err(e)); }
+                Some(Ok((k, v))) => {
+                    if let Bound::Excluded(b) = self.lo {
+                        if &k == b { continue; }
+                    }
+                    let in_range = match self.hi {
+                        Bound::Unbounded => true,
+                        Bound::Included(b) => &k <= b,
+                        Bound::Excluded(b) => &k < b,
+                    };
+                    if !in_range {
+                        self.done = true;
+                        return None;
+                    }
+                    return Some(Ok((k, v)));
+                }
+            }
+        }
+    }
+}
+
 pub extern "C" fn sort<T:FromMdbValue+Ord>(lhs_val: *const MDB_val, rhs_val: *const MDB_val) -> lmdb::c_int {
     let lhs = T::from_mdb_value(&unsafe{MdbValue::from_raw(lhs_val)});
     let rhs = T::from_mdb_value(&unsafe{MdbValue::from_raw(rhs_val)});
@@ -267,6 +541,82 @@ pub extern "C" fn sort_reverse<T:FromMdbValue+Ord>(lhs_val: *const MDB_val, rhs_
     order as lmdb::c_int
 }
 
+/// Describes one fixed-width field within a composite key's raw byte
+/// layout, for use with `cmp_fields`/`compare_key!`.
+///
+/// `offset` and `width` are byte positions within the raw key buffer;
+/// `ascending` selects the sort direction for that field alone.
+pub struct FieldSpec {
+    pub offset: usize,
+    pub width: usize,
+    pub ascending: bool,
+}
+
+/// Compares two raw keys field by field according to `fields`, returning
+/// the `-1`/`0`/`1` result expected by `mdb_set_compare`.
+///
+/// Fields are compared in order, most-significant first; the first field
+/// whose bytes differ decides the outcome, with its sign flipped when
+/// `ascending` is `false`. Each field's bytes are compared lexicographically,
+/// so a multi-byte integer field sorts numerically only when it is stored
+/// big-endian. Intended to be called from an `extern "C"`
+/// comparator generated by `compare_key!`.
+///
+/// A key shorter than a field's declared `offset + width` violates the
+/// `FieldSpec` layout precondition; such a field is skipped rather than
+/// indexed out of bounds, since panicking here would unwind through
+/// LMDB's C call frame, which is undefined behavior.
+pub unsafe fn cmp_fields(lhs_val: *const MDB_val, rhs_val: *const MDB_val, fields: &[FieldSpec]) -> lmdb::c_int {
+    let lhs = MdbValue::from_raw(lhs_val);
+    let rhs = MdbValue::from_raw(rhs_val);
+    let lhs_bytes = std::slice::from_raw_parts(lhs.get_ref() as *const u8, lhs.get_size());
+    let rhs_bytes = std::slice::from_raw_parts(rhs.get_ref() as *const u8, rhs.get_size());
+
+    for field in fields {
+        let end = field.offset + field.width;
+        if end > lhs_bytes.len() || end > rhs_bytes.len() {
+            continue;
+        }
+        let l = &lhs_bytes[field.offset .. end];
+        let r = &rhs_bytes[field.offset .. end];
+        let mut order = l.cmp(r);
+        if !field.ascending {
+            order = order.reverse();
+        }
+        if order != std::cmp::Ordering::Equal {
+            return match order {
+                std::cmp::Ordering::Less => -1,
+                std::cmp::Ordering::Equal => 0,
+                std::cmp::Ordering::Greater => 1,
+            };
+        }
+    }
+    0
+}
+
+/// Generates an `extern "C"` key comparator from a declared composite-key
+/// layout, suitable for `db.set_compare(...)` in `TableDef::setup`.
+///
+/// ```ignore
+/// compare_key!(cmp_my_key, [
+///     (0, 8, true),   // field A: big-endian u64, ascending
+///     (8, 8, false),  // field B: big-endian u64, descending
+/// ]);
+/// ```
+#[macro_export]
+macro_rules! compare_key {
+    (
+        $name:ident, [ $(($offset:expr, $width:expr, $ascending:expr)),* $(,)* ]
+    ) => {
+        pub extern "C" fn $name(lhs_val: *const ::lmdb::MDB_val, rhs_val: *const ::lmdb::MDB_val) -> ::lmdb::c_int {
+            let fields = [
+                $(FieldSpec {offset: $offset, width: $width, ascending: $ascending}),*
+            ];
+            unsafe { cmp_fields(lhs_val, rhs_val, &fields) }
+        }
+    };
+}
+
 #[test]
 fn test_simple_table() {
     use std::path::Path;
@@ -375,3 +725,223 @@ fn test_simple_table() {
         }
     }
 }
+
+#[test]
+fn test_typed_iter() {
+    use std::path::Path;
+    use lmdb::core::DbIntKey;
+
+    let env = lmdb::EnvBuilder::new().max_dbs(1).autocreate_dir(true).open(&Path::new("./test/db2"), 0o777).unwrap();
+
+    struct IterTable;
+    impl TableDef for IterTable {
+        fn name() -> &'static str { "iter_table" }
+        fn flags() -> DbFlags { DbIntKey }
+        fn setup(_db: &Database) -> MdbResult<()> { Ok(()) }
+    }
+    impl_table!(IterTable, u64, u64);
+
+    let handle = IterTable::open(&env, true).unwrap();
+    {
+        let txn = env.new_transaction().unwrap();
+        {
+            let table = IterTable::table(txn.bind(&handle)).unwrap();
+            for &(k, v) in &[(1u64, 10u64), (2, 20), (3, 30), (5, 50)] {
+                table.set(&k, &v).unwrap();
+            }
+        }
+        txn.commit().unwrap();
+    }
+
+    let rdr = env.get_reader().unwrap();
+    let table = IterTable::table(rdr.bind(&handle)).unwrap();
+
+    let all: Vec<(u64, u64)> = table.iter().unwrap().map(|r| r.unwrap()).collect();
+    assert_eq!(all, vec![(1, 10), (2, 20), (3, 30), (5, 50)]);
+
+    // iter_from must include the matching key itself, not just the items after it.
+    let from: Vec<(u64, u64)> = table.iter_from(&2).unwrap().map(|r| r.unwrap()).collect();
+    assert_eq!(from, vec![(2, 20), (3, 30), (5, 50)]);
+
+    let ranged: Vec<(u64, u64)> = table.range(Bound::Included(&2), Bound::Excluded(&5)).unwrap()
+        .map(|r| r.unwrap()).collect();
+    assert_eq!(ranged, vec![(2, 20), (3, 30)]);
+}
+
+#[test]
+fn test_typed_iter_dup_sort() {
+    use std::path::Path;
+    use lmdb::core::{DbIntKey, DbAllowDups, DbAllowIntDups, DbDupFixed};
+
+    let env = lmdb::EnvBuilder::new().max_dbs(1).autocreate_dir(true).open(&Path::new("./test/db2b"), 0o777).unwrap();
+
+    struct DupIterTable;
+    impl TableDef for DupIterTable {
+        fn name() -> &'static str { "dup_iter_table" }
+        fn flags() -> DbFlags { DbIntKey | DbAllowDups | DbAllowIntDups | DbDupFixed }
+        fn setup(_db: &Database) -> MdbResult<()> { Ok(()) }
+    }
+    impl_table!(DupIterTable, u64, u64);
+
+    let handle = DupIterTable::open(&env, true).unwrap();
+    {
+        let txn = env.new_transaction().unwrap();
+        {
+            let table = DupIterTable::table(txn.bind(&handle)).unwrap();
+            for &(k, v) in &[(1u64, 10u64), (2, 20), (2, 21), (3, 30), (4, 40)] {
+                table.set(&k, &v).unwrap();
+            }
+        }
+        txn.commit().unwrap();
+    }
+
+    let rdr = env.get_reader().unwrap();
+    let table = DupIterTable::table(rdr.bind(&handle)).unwrap();
+
+    // iter_from must walk all of a key's duplicates and then keep going
+    // into the following keys, not stop once the starting key's
+    // duplicates are exhausted.
+    let from: Vec<(u64, u64)> = table.iter_from(&2).unwrap().map(|r| r.unwrap()).collect();
+    assert_eq!(from, vec![(2, 20), (2, 21), (3, 30), (4, 40)]);
+}
+
+#[test]
+fn test_cmp_fields() {
+    use std::path::Path;
+
+    compare_key!(cmp_composite, [
+        (0, 1, true),   // field A: 1 byte, ascending
+        (1, 1, false),  // field B: 1 byte, descending
+    ]);
+
+    let env = lmdb::EnvBuilder::new().max_dbs(1).autocreate_dir(true).open(&Path::new("./test/db3"), 0o777).unwrap();
+
+    struct CompositeTable;
+    impl TableDef for CompositeTable {
+        fn name() -> &'static str { "composite_table" }
+        fn flags() -> DbFlags { DbFlags::empty() }
+        fn setup(db: &Database) -> MdbResult<()> {
+            db.set_compare(cmp_composite)
+        }
+    }
+    impl CompositeTable {
+        fn table<'db>(db: Database<'db>) -> MdbResult<Table<'db, &'db [u8], u64>> {
+            try!(Self::setup(&db));
+            Ok(Table{db: db, k: PhantomData, v: PhantomData})
+        }
+    }
+
+    let handle = CompositeTable::open(&env, true).unwrap();
+    {
+        let txn = env.new_transaction().unwrap();
+        {
+            let table = CompositeTable::table(txn.bind(&handle)).unwrap();
+            // Same field A, differing field B; byte order alone would put
+            // these ascending, but field B is declared descending above.
+            let k1: &[u8] = &[1, 1];
+            let k2: &[u8] = &[1, 2];
+            let k3: &[u8] = &[1, 3];
+            table.set(&k1, &1).unwrap();
+            table.set(&k2, &2).unwrap();
+            table.set(&k3, &3).unwrap();
+        }
+        txn.commit().unwrap();
+    }
+
+    let rdr = env.get_reader().unwrap();
+    let table = CompositeTable::table(rdr.bind(&handle)).unwrap();
+    let seen: Vec<u64> = table.iter().unwrap().map(|r| r.unwrap().1).collect();
+    assert_eq!(seen, vec![3, 2, 1]);
+}
+
+#[test]
+fn test_archived_table() {
+    use std::path::Path;
+    use lmdb::core::DbIntKey;
+
+    #[derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)]
+    struct Point { x: i32, y: i32 }
+
+    let env = lmdb::EnvBuilder::new().max_dbs(1).autocreate_dir(true).open(&Path::new("./test/db4"), 0o777).unwrap();
+
+    struct PointTable;
+    impl TableDef for PointTable {
+        fn name() -> &'static str { "point_table" }
+        fn flags() -> DbFlags { DbIntKey }
+        fn setup(_db: &Database) -> MdbResult<()> { Ok(()) }
+    }
+    impl PointTable {
+        fn table<'db>(db: Database<'db>) -> MdbResult<Table<'db, u64, Point>> {
+            try!(Self::setup(&db));
+            Ok(Table{db: db, k: PhantomData, v: PhantomData})
+        }
+    }
+
+    let handle = PointTable::open(&env, true).unwrap();
+    {
+        let txn = env.new_transaction().unwrap();
+        {
+            let table = PointTable::table(txn.bind(&handle)).unwrap();
+            table.set_archived(&1, &Point {x: 3, y: 4}).unwrap();
+            table.set_archived(&2, &Point {x: 5, y: 6}).unwrap();
+        }
+        txn.commit().unwrap();
+    }
+
+    let rdr = env.get_reader().unwrap();
+    let table = PointTable::table(rdr.bind(&handle)).unwrap();
+
+    let p = table.get_archived(&1).unwrap();
+    assert_eq!(p.x, 3);
+    assert_eq!(p.y, 4);
+
+    let all: Vec<(u64, i32)> = table.iter_archived().unwrap()
+        .map(|r| { let (k, p) = r.unwrap(); (k, p.x) }).collect();
+    assert_eq!(all, vec![(1, 3), (2, 5)]);
+}
+
+#[test]
+fn test_append_bulk_load() {
+    use std::path::Path;
+    use lmdb::core::{DbIntKey, DbAllowDups, DbAllowIntDups, DbDupFixed};
+
+    let env = lmdb::EnvBuilder::new().max_dbs(1).autocreate_dir(true).open(&Path::new("./test/db6"), 0o777).unwrap();
+
+    struct BulkTable;
+    impl TableDef for BulkTable {
+        fn name() -> &'static str { "bulk_table" }
+        fn flags() -> DbFlags { DbIntKey | DbAllowDups | DbAllowIntDups | DbDupFixed }
+        fn setup(_db: &Database) -> MdbResult<()> { Ok(()) }
+    }
+    impl_table!(BulkTable, u64, u64);
+
+    let handle = BulkTable::open(&env, true).unwrap();
+    {
+        let txn = env.new_transaction().unwrap();
+        {
+            let table = BulkTable::table(txn.bind(&handle)).unwrap();
+            table.append(&1, &10).unwrap();
+            table.append(&2, &20).unwrap();
+            table.append_dup(&2, &21).unwrap();
+            table.extend(vec![(3, 30), (4, 40)]).unwrap();
+        }
+        txn.commit().unwrap();
+    }
+
+    let rdr = env.get_reader().unwrap();
+    let table = BulkTable::table(rdr.bind(&handle)).unwrap();
+
+    // Verified directly via has_item/get, independent of TypedIter, so
+    // this test asserts append/append_dup/extend wrote the right rows
+    // regardless of whether the iterator itself is correct.
+    assert!(table.has_item(&1, &10).unwrap());
+    assert!(table.has_item(&2, &20).unwrap());
+    assert!(table.has_item(&2, &21).unwrap());
+    assert!(table.has_item(&3, &30).unwrap());
+    assert!(table.has_item(&4, &40).unwrap());
+    assert!(!table.has_item(&2, &99).unwrap());
+    assert_eq!(table.get(&1).unwrap(), 10);
+
+    let all: Vec<(u64, u64)> = table.iter().unwrap().map(|r| r.unwrap()).collect();
+    assert_eq!(all, vec![(1, 10), (2, 20), (2, 21), (3, 30), (4, 40)]);
+}